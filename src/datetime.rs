@@ -0,0 +1,55 @@
+//! Optional `Value` -> datetime conversions, enabled by the `chrono` feature.
+//!
+//! SQLite has no native date/time type: values are stored as ISO-8601 text, Unix-epoch
+//! integers, or Julian day reals (<https://www.sqlite.org/lang_datefunc.html>). This module
+//! lets any of the three be read through [`FromSql`] as a `chrono` type.
+#![cfg(feature = "chrono")]
+
+use chrono::{DateTime, NaiveDateTime, TimeZone, Utc};
+
+use crate::from_sql::{FromSql, FromSqlError};
+use crate::record::Value;
+
+/// Julian day number of the Unix epoch (1970-01-01T00:00:00Z).
+const UNIX_EPOCH_JULIAN_DAY: f64 = 2440587.5;
+
+const DATETIME_FORMATS: &[&str] = &["%Y-%m-%d %H:%M:%S%.f", "%Y-%m-%dT%H:%M:%S%.fZ"];
+
+fn parse_text(s: &str) -> Result<NaiveDateTime, FromSqlError> {
+    DATETIME_FORMATS
+        .iter()
+        .find_map(|format| NaiveDateTime::parse_from_str(s, format).ok())
+        .ok_or_else(|| FromSqlError::InvalidType {
+            expected: "datetime text",
+            value: Value::Text(s.to_owned()),
+        })
+}
+
+fn julian_day_to_naive(jd: f64) -> Result<NaiveDateTime, FromSqlError> {
+    let unix_secs = (jd - UNIX_EPOCH_JULIAN_DAY) * 86400.0;
+    DateTime::from_timestamp(unix_secs as i64, 0)
+        .map(|dt| dt.naive_utc())
+        .ok_or(FromSqlError::IntegralValueOutOfRange(unix_secs as i64))
+}
+
+impl FromSql for NaiveDateTime {
+    fn from_value(value: &Value) -> Result<Self, FromSqlError> {
+        match value {
+            Value::Text(s) => parse_text(s),
+            Value::Integer(secs) => DateTime::from_timestamp(*secs, 0)
+                .map(|dt| dt.naive_utc())
+                .ok_or(FromSqlError::IntegralValueOutOfRange(*secs)),
+            Value::Real(jd) => julian_day_to_naive(*jd),
+            _ => Err(FromSqlError::InvalidType {
+                expected: "NaiveDateTime",
+                value: value.clone(),
+            }),
+        }
+    }
+}
+
+impl FromSql for DateTime<Utc> {
+    fn from_value(value: &Value) -> Result<Self, FromSqlError> {
+        NaiveDateTime::from_value(value).map(|naive| Utc.from_utc_datetime(&naive))
+    }
+}
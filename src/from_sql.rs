@@ -0,0 +1,124 @@
+use crate::record::{Record, Value};
+
+#[derive(Debug, thiserror::Error)]
+pub enum FromSqlError {
+    #[error("invalid type: expected {expected}, got {value:?}")]
+    InvalidType {
+        expected: &'static str,
+        value: Value,
+    },
+    #[error("integral value out of range: {0}")]
+    IntegralValueOutOfRange(i64),
+    #[error("no such column: {0}")]
+    ColumnNotFound(String),
+}
+
+pub trait FromSql: Sized {
+    fn from_value(value: &Value) -> Result<Self, FromSqlError>;
+}
+
+macro_rules! impl_from_sql_integer {
+    ($ty:ty) => {
+        impl FromSql for $ty {
+            fn from_value(value: &Value) -> Result<Self, FromSqlError> {
+                match value {
+                    Value::Integer(n) => {
+                        <$ty>::try_from(*n).map_err(|_| FromSqlError::IntegralValueOutOfRange(*n))
+                    }
+                    _ => Err(FromSqlError::InvalidType {
+                        expected: stringify!($ty),
+                        value: value.clone(),
+                    }),
+                }
+            }
+        }
+    };
+}
+
+impl_from_sql_integer!(i8);
+impl_from_sql_integer!(i16);
+impl_from_sql_integer!(i32);
+impl_from_sql_integer!(u32);
+
+impl FromSql for i64 {
+    fn from_value(value: &Value) -> Result<Self, FromSqlError> {
+        match value {
+            Value::Integer(n) => Ok(*n),
+            _ => Err(FromSqlError::InvalidType {
+                expected: "i64",
+                value: value.clone(),
+            }),
+        }
+    }
+}
+
+impl FromSql for f32 {
+    fn from_value(value: &Value) -> Result<Self, FromSqlError> {
+        match value {
+            Value::Real(f) => Ok(*f as f32),
+            Value::Integer(n) => Ok(*n as f32),
+            _ => Err(FromSqlError::InvalidType {
+                expected: "f32",
+                value: value.clone(),
+            }),
+        }
+    }
+}
+
+impl FromSql for f64 {
+    fn from_value(value: &Value) -> Result<Self, FromSqlError> {
+        match value {
+            Value::Real(f) => Ok(*f),
+            Value::Integer(n) => Ok(*n as f64),
+            _ => Err(FromSqlError::InvalidType {
+                expected: "f64",
+                value: value.clone(),
+            }),
+        }
+    }
+}
+
+impl FromSql for bool {
+    fn from_value(value: &Value) -> Result<Self, FromSqlError> {
+        match value {
+            Value::Integer(n) => Ok(*n != 0),
+            _ => Err(FromSqlError::InvalidType {
+                expected: "bool",
+                value: value.clone(),
+            }),
+        }
+    }
+}
+
+impl FromSql for String {
+    fn from_value(value: &Value) -> Result<Self, FromSqlError> {
+        match value {
+            Value::Text(s) => Ok(s.clone()),
+            _ => Err(FromSqlError::InvalidType {
+                expected: "String",
+                value: value.clone(),
+            }),
+        }
+    }
+}
+
+impl FromSql for Vec<u8> {
+    fn from_value(value: &Value) -> Result<Self, FromSqlError> {
+        match value {
+            Value::Blob(bytes) => Ok(bytes.clone()),
+            _ => Err(FromSqlError::InvalidType {
+                expected: "Vec<u8>",
+                value: value.clone(),
+            }),
+        }
+    }
+}
+
+impl Record {
+    pub fn get<T: FromSql>(&self, column: &str) -> Result<T, FromSqlError> {
+        let value = self
+            .get_value(column)
+            .ok_or_else(|| FromSqlError::ColumnNotFound(column.to_string()))?;
+        T::from_value(value)
+    }
+}
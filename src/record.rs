@@ -1,11 +1,44 @@
 use nom::{bytes::complete::take, number::complete::i8, IResult};
-use std::collections::HashMap;
+use std::cmp::Ordering;
 
 use crate::varint::varint;
 
+/// A parsed record's column values, kept in physical (serial-type) order so that positional
+/// access matches how `SELECT *` and most SQL engines expect rows to be laid out.
 #[derive(Debug)]
 pub struct Record {
-    pub values: HashMap<String, Value>,
+    values: Vec<(String, Value)>,
+}
+
+impl Record {
+    /// The value stored in the given named column, if one exists.
+    pub fn get_value(&self, column: &str) -> Option<&Value> {
+        self.values
+            .iter()
+            .find(|(name, _)| name == column)
+            .map(|(_, value)| value)
+    }
+
+    /// The value stored at the given physical column position.
+    pub fn get_by_index(&self, idx: usize) -> Option<&Value> {
+        self.values.get(idx).map(|(_, value)| value)
+    }
+
+    /// The number of columns in this record.
+    pub fn len(&self) -> usize {
+        self.values.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.values.is_empty()
+    }
+
+    /// Iterates over `(column_name, value)` pairs in physical column order.
+    pub fn iter(&self) -> impl Iterator<Item = (&str, &Value)> {
+        self.values
+            .iter()
+            .map(|(name, value)| (name.as_str(), value))
+    }
 }
 
 #[derive(Debug)]
@@ -31,13 +64,13 @@ pub enum Value {
     Integer(i64),
     Real(f64),
     Text(String),
-    Blob(String),
+    Blob(Vec<u8>),
 }
 
 impl PartialEq for Value {
     fn eq(&self, other: &Self) -> bool {
         match self {
-            Value::Null => false,
+            Value::Null => matches!(other, Value::Null),
             Value::Integer(n1) => match other {
                 Value::Integer(n2) => n1 == n2,
                 _ => false,
@@ -48,18 +81,84 @@ impl PartialEq for Value {
             },
             Value::Text(s1) => match other {
                 Value::Text(s2) => s1 == s2,
-                Value::Blob(s2) => s1 == s2,
                 _ => false,
             },
-            Value::Blob(s1) => match other {
-                Value::Text(s2) => s1 == s2,
-                Value::Blob(s2) => s1 == s2,
+            Value::Blob(b1) => match other {
+                Value::Blob(b2) => b1 == b2,
                 _ => false,
             },
         }
     }
 }
 
+/// The text collating function used when comparing two `Value::Text`s, mirroring SQLite's
+/// built-in collations (<https://www.sqlite.org/datatype3.html#collating_sequences>).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Collation {
+    /// Compares text byte-for-byte. SQLite's default collation.
+    Binary,
+    /// Compares text byte-for-byte, except ASCII uppercase letters are folded to lowercase.
+    NoCase,
+    /// Like `Binary`, but trailing whitespace is ignored.
+    RTrim,
+}
+
+impl Collation {
+    fn compare(&self, s1: &str, s2: &str) -> Ordering {
+        match self {
+            Collation::Binary => s1.as_bytes().cmp(s2.as_bytes()),
+            Collation::NoCase => s1.to_ascii_lowercase().cmp(&s2.to_ascii_lowercase()),
+            Collation::RTrim => s1.trim_end().as_bytes().cmp(s2.trim_end().as_bytes()),
+        }
+    }
+}
+
+impl Value {
+    /// The relative rank of this value's storage class, per SQLite's sort order:
+    /// NULL < INTEGER/REAL < TEXT < BLOB.
+    fn storage_class_rank(&self) -> u8 {
+        match self {
+            Value::Null => 0,
+            Value::Integer(_) | Value::Real(_) => 1,
+            Value::Text(_) => 2,
+            Value::Blob(_) => 3,
+        }
+    }
+
+    /// Compares two values using SQLite's documented sort order, with `collation` used to
+    /// order `Text` values against each other.
+    pub fn compare(&self, other: &Self, collation: Collation) -> Ordering {
+        match (self, other) {
+            (Value::Null, Value::Null) => Ordering::Equal,
+            (Value::Integer(n1), Value::Integer(n2)) => n1.cmp(n2),
+            (Value::Real(f1), Value::Real(f2)) => f1.partial_cmp(f2).unwrap_or(Ordering::Equal),
+            (Value::Integer(n1), Value::Real(f2)) => {
+                (*n1 as f64).partial_cmp(f2).unwrap_or(Ordering::Equal)
+            }
+            (Value::Real(f1), Value::Integer(n2)) => {
+                f1.partial_cmp(&(*n2 as f64)).unwrap_or(Ordering::Equal)
+            }
+            (Value::Text(s1), Value::Text(s2)) => collation.compare(s1, s2),
+            (Value::Blob(b1), Value::Blob(b2)) => b1.cmp(b2),
+            _ => self.storage_class_rank().cmp(&other.storage_class_rank()),
+        }
+    }
+}
+
+impl Eq for Value {}
+
+impl Ord for Value {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.compare(other, Collation::Binary)
+    }
+}
+
+impl PartialOrd for Value {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
 impl Value {
     pub fn as_integer(&self) -> Option<i64> {
         match self {
@@ -84,9 +183,9 @@ impl Value {
     }
 
     #[allow(dead_code)]
-    pub fn as_blob(&self) -> Option<&str> {
+    pub fn as_blob(&self) -> Option<&[u8]> {
         match self {
-            Value::Blob(s) => Some(s),
+            Value::Blob(bytes) => Some(bytes),
             _ => None,
         }
     }
@@ -98,7 +197,7 @@ impl ToString for Value {
             Value::Null => "null".into(),
             Value::Integer(n) => n.to_string(),
             Value::Real(f) => f.to_string(),
-            Value::Blob(s) => s.to_owned(),
+            Value::Blob(bytes) => bytes.iter().map(|b| format!("{:02x}", b)).collect(),
             Value::Text(s) => s.to_owned(),
         }
     }
@@ -156,10 +255,19 @@ pub enum RecordType {
     Index,
 }
 
+/// Describes one column of a table being parsed: its name, and whether it is the column that
+/// `CREATE TABLE` declared `INTEGER PRIMARY KEY`, in which case it is a rowid alias rather than
+/// a column with its own storage.
+#[derive(Debug, Clone)]
+pub struct ColumnDescriptor {
+    pub name: String,
+    pub is_rowid_alias: bool,
+}
+
 impl Record {
     pub fn parse<'input>(
         input: &'input [u8],
-        column_names: &[String],
+        columns: &[ColumnDescriptor],
         record_type: RecordType,
     ) -> IResult<&'input [u8], Self> {
         let (input, row_id) = if record_type == RecordType::Table {
@@ -176,7 +284,7 @@ impl Record {
 
         let mut rest = input;
         let mut column_types = Vec::new();
-        for _ in 0..column_names.len() {
+        for _ in 0..columns.len() {
             let (remainder, column_type) = varint(rest)?;
             header_bytes_read += rest.len() - remainder.len();
             rest = remainder;
@@ -185,81 +293,100 @@ impl Record {
         }
         assert_eq!(header_bytes_read, header_size as usize);
 
-        let mut values = HashMap::new();
-        for (column_name, column_type) in column_names.iter().zip(column_types.iter()) {
+        let mut values: Vec<(String, Value)> = Vec::new();
+        for (column, column_type) in columns.iter().zip(column_types.iter()) {
+            let column_name = &column.name;
             match column_type {
                 ColumnType::Null => {
-                    if record_type == RecordType::Table && column_name == "id" {
-                        // FIXME: This is only an alias for `rowid` when it has type
-                        // "INTEGER PRIMARY KEY" - it's not based on the name
-                        values.insert(column_name.to_string(), Value::Integer(row_id.unwrap()));
+                    if record_type == RecordType::Table && column.is_rowid_alias {
+                        values.push((column_name.to_string(), Value::Integer(row_id.unwrap())));
                     } else {
-                        values.insert(column_name.to_string(), Value::Null);
+                        values.push((column_name.to_string(), Value::Null));
                     }
                 }
                 ColumnType::I8 => {
                     let (remainder, value) = i8(rest)?;
                     rest = remainder;
-                    values.insert(column_name.to_string(), Value::Integer(value as i64));
+                    values.push((column_name.to_string(), Value::Integer(value as i64)));
                 }
                 ColumnType::I16 => {
                     let (remainder, bytes) = take(2usize)(rest)?;
                     rest = remainder;
-                    values.insert(
+                    values.push((
                         column_name.to_string(),
                         Value::Integer(i16::from_be_bytes([bytes[0], bytes[1]]) as i64),
-                    );
+                    ));
                 }
                 ColumnType::I24 => {
                     let (remainder, bytes) = take(3usize)(rest)?;
                     rest = remainder;
-                    values.insert(
+                    values.push((
                         column_name.to_string(),
                         Value::Integer(i32::from_be_bytes([0, bytes[0], bytes[1], bytes[2]]) as i64),
-                    );
+                    ));
                 }
                 ColumnType::I32 => {
                     let (remainder, bytes) = take(4usize)(rest)?;
                     rest = remainder;
-                    values.insert(
+                    values.push((
                         column_name.to_string(),
                         Value::Integer(
                             i32::from_be_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]) as i64,
                         ),
-                    );
+                    ));
+                }
+                ColumnType::I48 => {
+                    let (remainder, bytes) = take(6usize)(rest)?;
+                    rest = remainder;
+                    let sign_extend = if bytes[0] & 0x80 != 0 { 0xff } else { 0x00 };
+                    values.push((
+                        column_name.to_string(),
+                        Value::Integer(i64::from_be_bytes([
+                            sign_extend,
+                            sign_extend,
+                            bytes[0],
+                            bytes[1],
+                            bytes[2],
+                            bytes[3],
+                            bytes[4],
+                            bytes[5],
+                        ])),
+                    ));
+                }
+                ColumnType::I64 => {
+                    let (remainder, bytes) = take(8usize)(rest)?;
+                    rest = remainder;
+                    values.push((
+                        column_name.to_string(),
+                        Value::Integer(i64::from_be_bytes(bytes.try_into().unwrap())),
+                    ));
+                }
+                ColumnType::F64 => {
+                    let (remainder, bytes) = take(8usize)(rest)?;
+                    rest = remainder;
+                    values.push((
+                        column_name.to_string(),
+                        Value::Real(f64::from_be_bytes(bytes.try_into().unwrap())),
+                    ));
                 }
-                ColumnType::I48 => todo!("i48 column"),
-                ColumnType::I64 => todo!("i64 column"),
-                ColumnType::F64 => todo!("f64 column"),
                 ColumnType::Zero => {
-                    values.insert(column_name.to_string(), Value::Integer(0i64));
+                    values.push((column_name.to_string(), Value::Integer(0i64)));
                 }
                 ColumnType::One => {
-                    values.insert(column_name.to_string(), Value::Integer(0i64));
+                    values.push((column_name.to_string(), Value::Integer(1i64)));
                 }
                 ColumnType::Blob(size) => {
                     let (remainder, bytes) = take(*size)(rest)?;
                     rest = remainder;
-                    values.insert(
-                        column_name.to_string(),
-                        Value::Blob(
-                            std::str::from_utf8(bytes)
-                                .expect("non utf-8 text")
-                                .to_owned(),
-                        ),
-                    );
+                    values.push((column_name.to_string(), Value::Blob(bytes.to_vec())));
                 }
                 ColumnType::Text(size) => {
                     let (remainder, bytes) = take(*size)(rest)?;
                     rest = remainder;
-                    values.insert(
+                    values.push((
                         column_name.to_string(),
-                        Value::Text(
-                            std::str::from_utf8(bytes)
-                                .expect("non utf-8 text")
-                                .to_owned(),
-                        ),
-                    );
+                        Value::Text(String::from_utf8_lossy(bytes).into_owned()),
+                    ));
                 }
             }
         }
@@ -267,3 +394,18 @@ impl Record {
         Ok((rest, Record { values }))
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn text_and_blob_with_equal_bytes_are_different_storage_classes() {
+        let text = Value::Text("abc".to_string());
+        let blob = Value::Blob(vec![b'a', b'b', b'c']);
+
+        assert_ne!(text, blob);
+        assert_eq!(text.compare(&blob, Collation::Binary), Ordering::Less);
+        assert_ne!(text.compare(&blob, Collation::Binary), Ordering::Equal);
+    }
+}